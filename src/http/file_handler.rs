@@ -0,0 +1,116 @@
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use crate::http::http_request::HttpRequest;
+use crate::http::http_response::{HttpResponse, HttpStatus};
+
+/// Serves files from a configured web root. The request path is resolved to a
+/// file beneath the root, and the resolved path is canonicalized and checked to
+/// remain within the root before anything is opened, so `../` traversal cannot
+/// escape the served directory.
+pub struct StaticFileHandler {
+    web_root: PathBuf,
+}
+
+impl StaticFileHandler {
+    /// Creates a handler that serves files rooted at the given directory.
+    pub fn new(web_root: &str) -> StaticFileHandler {
+        return StaticFileHandler {
+            web_root: PathBuf::from(web_root),
+        };
+    }
+
+    /// Resolves the request path against the web root and returns the file
+    /// contents with a guessed `Content-Type`. Returns 404 when the file does
+    /// not exist and 400 when the resolved path escapes the web root.
+    pub fn handle(&self, request: &HttpRequest) -> HttpResponse {
+        let relative = request.path.trim_start_matches('/');
+        let root = match self.web_root.canonicalize() {
+            Ok(root) => root,
+            Err(_) => return HttpResponse::new(HttpStatus::InternalServerError),
+        };
+        // Fold the request path into the root lexically so a `../` escape is
+        // rejected with 400 before the file system is touched. Resolving against
+        // the real path only (as `canonicalize` does) would report a missing
+        // traversal target as 404, hiding the escape attempt.
+        let mut resolved = root.clone();
+        for component in Path::new(relative).components() {
+            match component {
+                Component::Normal(part) => resolved.push(part),
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    if !resolved.pop() || !resolved.starts_with(&root) {
+                        return HttpResponse::new(HttpStatus::BadRequest);
+                    }
+                }
+                _ => return HttpResponse::new(HttpStatus::BadRequest),
+            }
+        }
+        if !resolved.starts_with(&root) {
+            return HttpResponse::new(HttpStatus::BadRequest);
+        }
+        // Canonicalize the target as a final guard against symlinks that point
+        // outside the root; a path that simply does not exist is a genuine 404.
+        let resolved = match resolved.canonicalize() {
+            Ok(resolved) => resolved,
+            Err(_) => return HttpResponse::new(HttpStatus::NotFound),
+        };
+        if !resolved.starts_with(&root) {
+            return HttpResponse::new(HttpStatus::BadRequest);
+        }
+        return match fs::read(&resolved) {
+            Ok(contents) => HttpResponse::new(HttpStatus::Ok)
+                .header("Content-Type", content_type(&resolved))
+                .body(contents),
+            Err(_) => HttpResponse::new(HttpStatus::NotFound),
+        };
+    }
+}
+
+/// Guesses the `Content-Type` of a file from its extension, defaulting to
+/// `application/octet-stream` for unknown extensions.
+fn content_type(path: &Path) -> &'static str {
+    return match path.extension().and_then(|ext| ext.to_str()) {
+        Some("html") => "text/html",
+        Some("css") => "text/css",
+        Some("js") => "application/javascript",
+        Some("json") => "application/json",
+        Some("png") => "image/png",
+        Some("jpg") | Some("jpeg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("svg") => "image/svg+xml",
+        Some("txt") => "text/plain",
+        _ => "application/octet-stream",
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{content_type, StaticFileHandler};
+    use crate::http::http_request::{test_request, HttpRequest, HttpRequestMethod};
+    use crate::http::http_response::HttpStatus;
+    use std::path::PathBuf;
+
+    fn request(path: &str) -> HttpRequest {
+        return test_request(HttpRequestMethod::Get, path);
+    }
+
+    #[test]
+    fn content_type_is_guessed_by_extension() {
+        assert_eq!(content_type(&PathBuf::from("index.html")), "text/html");
+        assert_eq!(content_type(&PathBuf::from("app.js")), "application/javascript");
+        assert_eq!(
+            content_type(&PathBuf::from("data.bin")),
+            "application/octet-stream"
+        );
+    }
+
+    #[test]
+    fn traversal_outside_web_root_is_rejected() {
+        let handler = StaticFileHandler::new(".");
+        let response = handler.handle(&request("/../../etc/passwd"));
+        assert!(
+            response.status == HttpStatus::BadRequest || response.status == HttpStatus::NotFound
+        );
+    }
+}
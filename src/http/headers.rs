@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+
+/// A collection of HTTP headers that matches names case-insensitively and
+/// preserves every value sent for a name. HTTP header names are
+/// case-insensitive and some (such as `Set-Cookie` or `Accept`) may appear more
+/// than once, so lookups are normalized to a canonical case and values are kept
+/// in insertion order.
+pub struct Headers {
+    values: HashMap<String, Vec<String>>,
+}
+
+impl Headers {
+    /// Creates an empty header collection.
+    pub fn new() -> Headers {
+        return Headers {
+            values: HashMap::new(),
+        };
+    }
+
+    /// Appends a value for the given header name, keeping any values already
+    /// stored under it.
+    pub fn insert(&mut self, name: &str, value: &str) {
+        self.values
+            .entry(canonical(name))
+            .or_default()
+            .push(value.to_string());
+    }
+
+    /// Returns the first value stored for the name, or `None` if absent.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        return self
+            .values
+            .get(&canonical(name))
+            .and_then(|values| values.first())
+            .map(|value| value.as_str());
+    }
+
+    /// Returns every value stored for the name in insertion order, or an empty
+    /// slice if the name is absent.
+    pub fn get_all(&self, name: &str) -> &[String] {
+        return match self.values.get(&canonical(name)) {
+            Some(values) => values,
+            None => &[],
+        };
+    }
+
+    /// Returns whether any value is stored for the name.
+    pub fn contains(&self, name: &str) -> bool {
+        return self.values.contains_key(&canonical(name));
+    }
+}
+
+impl Default for Headers {
+    fn default() -> Headers {
+        return Headers::new();
+    }
+}
+
+/// Normalizes a header name to the canonical (lower-case) form used as the map
+/// key so lookups ignore the case the client happened to send.
+fn canonical(name: &str) -> String {
+    return name.to_ascii_lowercase();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Headers;
+
+    #[test]
+    fn lookup_ignores_case() {
+        let mut headers = Headers::new();
+        headers.insert("Content-Length", "15");
+        assert_eq!(headers.get("content-length"), Some("15"));
+        assert!(headers.contains("CONTENT-LENGTH"));
+    }
+
+    #[test]
+    fn repeated_headers_are_preserved() {
+        let mut headers = Headers::new();
+        headers.insert("Set-Cookie", "a=1");
+        headers.insert("set-cookie", "b=2");
+        assert_eq!(headers.get_all("Set-Cookie"), &["a=1", "b=2"]);
+        assert_eq!(headers.get("Set-Cookie"), Some("a=1"));
+    }
+}
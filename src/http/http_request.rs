@@ -1,25 +1,72 @@
-use std::{collections::HashMap, io::BufRead};
+use std::{
+    collections::HashMap,
+    io::BufRead,
+};
 
+use crate::http::headers::Headers;
+
+#[derive(Debug)]
 pub enum HttpParseError {
     ParseStartLineError,
-    ParseHeaderError,
+    StartLineMissingMethod,
+    StartLineMissingTarget,
+    MethodNotSupported(String),
+    MalformedHeader(String),
     ReadBodyError,
 }
 
+impl HttpParseError {
+    /// Returns a human-readable description of the parse failure, suitable for
+    /// logging or for the body of a `400 Bad Request` response.
+    pub fn description(&self) -> String {
+        return match self {
+            HttpParseError::ParseStartLineError => {
+                "failed to read the request start line".to_string()
+            }
+            HttpParseError::StartLineMissingMethod => {
+                "request start line is missing a method".to_string()
+            }
+            HttpParseError::StartLineMissingTarget => {
+                "request start line is missing a target".to_string()
+            }
+            HttpParseError::MethodNotSupported(method) => {
+                format!("unsupported request method: {}", method)
+            }
+            HttpParseError::MalformedHeader(header) => {
+                format!("malformed header: {}", header)
+            }
+            HttpParseError::ReadBodyError => "failed to read the request body".to_string(),
+        };
+    }
+}
+
 pub struct HttpRequest {
     pub method: HttpRequestMethod,
     pub path: String,
-    pub headers: HashMap<String, String>,
-    pub body: String,
+    pub headers: Headers,
+    pub body: Vec<u8>,
+    pub params: HashMap<String, String>,
+    pub query: HashMap<String, String>,
 }
 
 pub fn parse_http_request<R: BufRead>(reader: &mut R) -> Result<HttpRequest, HttpParseError> {
+    let mut request = parse_http_request_head(reader)?;
+    request.body = read_http_body(reader, &request.headers)?;
+    return Ok(request);
+}
+
+/// Parses the request line and headers only, leaving the body empty. Splitting
+/// the head from the body lets the connection loop inspect headers such as
+/// `Expect: 100-continue` and emit an interim response before the body is read.
+pub fn parse_http_request_head<R: BufRead>(
+    reader: &mut R,
+) -> Result<HttpRequest, HttpParseError> {
     let mut line = String::new();
     if reader.read_line(&mut line).is_err() {
         return Err(HttpParseError::ParseStartLineError);
     }
-    let (method, path) = parse_http_start_line(line);
-    let mut headers: HashMap<String, String> = HashMap::new();
+    let (method, path, query) = parse_http_start_line(line)?;
+    let mut headers = Headers::new();
 
     let raw_headers: Vec<_> = reader
         .lines()
@@ -28,35 +75,82 @@ pub fn parse_http_request<R: BufRead>(reader: &mut R) -> Result<HttpRequest, Htt
         .collect();
 
     for raw_header in raw_headers {
-        match parse_http_header(raw_header) {
+        match parse_http_header(raw_header.clone()) {
             Some((name, value)) => {
-                headers.insert(name, value);
+                headers.insert(&name, &value);
             }
-            None => return Err(HttpParseError::ParseHeaderError),
+            None => return Err(HttpParseError::MalformedHeader(raw_header)),
         }
     }
 
-    let content_length: i32 = match headers.get("Content-Length") {
+    return Ok(HttpRequest {
+        method,
+        path,
+        headers,
+        body: Vec::new(),
+        params: HashMap::new(),
+        query,
+    });
+}
+
+/// Reads the request body according to the framing implied by the headers. A
+/// `Transfer-Encoding: chunked` header selects chunked decoding, otherwise
+/// exactly `Content-Length` bytes are read. The body is returned as raw bytes so
+/// non-UTF-8 payloads survive untouched.
+pub fn read_http_body<R: BufRead>(
+    reader: &mut R,
+    headers: &Headers,
+) -> Result<Vec<u8>, HttpParseError> {
+    if let Some(encoding) = headers.get("Transfer-Encoding") {
+        if encoding.eq_ignore_ascii_case("chunked") {
+            return read_chunked_body(reader);
+        }
+    }
+    let content_length: usize = match headers.get("Content-Length") {
         Some(header) => header.parse().unwrap_or(0),
         None => 0,
     };
-
-    let mut body_buffer: Vec<u8> = vec![0, content_length as u8];
-
-    if reader.read_to_end(&mut body_buffer).is_err() {
+    let mut body = vec![0u8; content_length];
+    if reader.read_exact(&mut body).is_err() {
         return Err(HttpParseError::ReadBodyError);
     }
+    return Ok(body);
+}
 
-    let body = String::from_utf8(body_buffer)
-        .unwrap_or(String::new())
-        .replace("\0\u{f}", "");
-
-    return Ok(HttpRequest {
-        method: method,
-        path: path,
-        headers: headers,
-        body: body,
-    });
+/// Reads a `Transfer-Encoding: chunked` body. Each chunk is prefixed by a line
+/// carrying its size in hexadecimal (an optional `;ext` suffix is ignored),
+/// followed by exactly that many bytes and a trailing CRLF. A zero-size chunk
+/// terminates the body. Returns `ReadBodyError` on a malformed size line or a
+/// stream that ends early.
+fn read_chunked_body<R: BufRead>(reader: &mut R) -> Result<Vec<u8>, HttpParseError> {
+    let mut body: Vec<u8> = Vec::new();
+    loop {
+        let mut size_line = String::new();
+        if reader.read_line(&mut size_line).is_err() {
+            return Err(HttpParseError::ReadBodyError);
+        }
+        let size_field = match size_line.trim_end().split_once(';') {
+            Some((size, _ext)) => size,
+            None => size_line.trim_end(),
+        };
+        let size = match usize::from_str_radix(size_field.trim(), 16) {
+            Ok(size) => size,
+            Err(_) => return Err(HttpParseError::ReadBodyError),
+        };
+        if size == 0 {
+            break;
+        }
+        let mut chunk = vec![0u8; size];
+        if reader.read_exact(&mut chunk).is_err() {
+            return Err(HttpParseError::ReadBodyError);
+        }
+        body.extend_from_slice(&chunk);
+        let mut crlf = [0u8; 2];
+        if reader.read_exact(&mut crlf).is_err() {
+            return Err(HttpParseError::ReadBodyError);
+        }
+    }
+    return Ok(body);
 }
 
 /// HTTP defines a set of request methods to indicate the desired action to be performed for a given resource.
@@ -64,7 +158,7 @@ pub fn parse_http_request<R: BufRead>(reader: &mut R) -> Result<HttpRequest, Htt
 /// Each of them implements a different semantic, but some common features are shared by a group of them:
 /// e.g. a request method can be safe, idempotent, or cacheable.
 /// source: https://developer.mozilla.org/en-US/docs/Web/HTTP/Methods
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum HttpRequestMethod {
     Get,
     Head,
@@ -77,32 +171,111 @@ pub enum HttpRequestMethod {
     Patch,
 }
 
-fn http_request_method_from_string(s: &str) -> HttpRequestMethod {
+impl HttpRequestMethod {
+    /// Returns the canonical upper-case name of the method as it appears on the
+    /// request start line and in the `Allow` header.
+    pub fn as_str(&self) -> &'static str {
+        return match self {
+            HttpRequestMethod::Get => "GET",
+            HttpRequestMethod::Head => "HEAD",
+            HttpRequestMethod::Post => "POST",
+            HttpRequestMethod::Put => "PUT",
+            HttpRequestMethod::Delete => "DELETE",
+            HttpRequestMethod::Connect => "CONNECT",
+            HttpRequestMethod::Options => "OPTIONS",
+            HttpRequestMethod::Trace => "TRACE",
+            HttpRequestMethod::Patch => "PATCH",
+        };
+    }
+}
+
+fn http_request_method_from_string(s: &str) -> Result<HttpRequestMethod, HttpParseError> {
     return match s.to_uppercase().as_str() {
-        "GET" => HttpRequestMethod::Get,
-        "HEAD" => HttpRequestMethod::Head,
-        "POST" => HttpRequestMethod::Post,
-        "PUT" => HttpRequestMethod::Put,
-        "DELETE" => HttpRequestMethod::Delete,
-        "CONNECT" => HttpRequestMethod::Connect,
-        "OPTIONS" => HttpRequestMethod::Options,
-        "TRACE" => HttpRequestMethod::Trace,
-        "Patch" => HttpRequestMethod::Patch,
-        _ => HttpRequestMethod::Get,
+        "GET" => Ok(HttpRequestMethod::Get),
+        "HEAD" => Ok(HttpRequestMethod::Head),
+        "POST" => Ok(HttpRequestMethod::Post),
+        "PUT" => Ok(HttpRequestMethod::Put),
+        "DELETE" => Ok(HttpRequestMethod::Delete),
+        "CONNECT" => Ok(HttpRequestMethod::Connect),
+        "OPTIONS" => Ok(HttpRequestMethod::Options),
+        "TRACE" => Ok(HttpRequestMethod::Trace),
+        "PATCH" => Ok(HttpRequestMethod::Patch),
+        _ => Err(HttpParseError::MethodNotSupported(s.to_string())),
     };
 }
 
-fn parse_http_start_line(s: String) -> (HttpRequestMethod, String) {
+fn parse_http_start_line(
+    s: String,
+) -> Result<(HttpRequestMethod, String, HashMap<String, String>), HttpParseError> {
     let mut parts = s.split(" ");
     let method = match parts.next() {
-        Some(method_str) => http_request_method_from_string(method_str),
-        None => HttpRequestMethod::Get,
+        Some(method_str) => http_request_method_from_string(method_str)?,
+        None => return Err(HttpParseError::StartLineMissingMethod),
     };
-    let path = match parts.next() {
-        Some(path) => path,
-        None => "/",
+    let target = match parts.next() {
+        Some(target) => target,
+        None => return Err(HttpParseError::StartLineMissingTarget),
     };
-    return (method, path.to_string());
+    let (path, query) = match target.split_once('?') {
+        Some((path, query)) => (path.to_string(), parse_query_string(query)),
+        None => (target.to_string(), HashMap::new()),
+    };
+    return Ok((method, path, query));
+}
+
+/// Parses a raw query string (the part of the target after `?`) into a map of
+/// decoded key/value pairs. Pairs are split on `&` and each pair on its first
+/// `=`; keys and values are percent-decoded. Valueless keys such as `?flag` map
+/// to an empty string and, when a key repeats, the last occurrence wins.
+fn parse_query_string(s: &str) -> HashMap<String, String> {
+    let mut query: HashMap<String, String> = HashMap::new();
+    for pair in s.split('&') {
+        if pair.is_empty() {
+            continue;
+        }
+        let (key, value) = match pair.split_once('=') {
+            Some((key, value)) => (key, value),
+            None => (pair, ""),
+        };
+        query.insert(percent_decode(key), percent_decode(value));
+    }
+    return query;
+}
+
+/// Decodes an `application/x-www-form-urlencoded` component: `%XX` hex escapes
+/// become their byte value and `+` becomes a space. Invalid escapes are left
+/// untouched. The resulting bytes are interpreted lossily as UTF-8.
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut decoded: Vec<u8> = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => {
+                decoded.push(b' ');
+                i += 1;
+            }
+            b'%' if i + 2 < bytes.len() => {
+                let high = (bytes[i + 1] as char).to_digit(16);
+                let low = (bytes[i + 2] as char).to_digit(16);
+                match (high, low) {
+                    (Some(high), Some(low)) => {
+                        decoded.push((high * 16 + low) as u8);
+                        i += 3;
+                    }
+                    _ => {
+                        decoded.push(bytes[i]);
+                        i += 1;
+                    }
+                }
+            }
+            byte => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    return String::from_utf8_lossy(&decoded).into_owned();
 }
 
 /// Parses an http_header from the given String.
@@ -122,6 +295,20 @@ fn parse_http_header(s: String) -> Option<(String, String)> {
     return Some((name, value));
 }
 
+/// Builds a bare request with empty headers, params and query for use in tests
+/// across the `http` module, sparing each test module its own copy.
+#[cfg(test)]
+pub(crate) fn test_request(method: HttpRequestMethod, path: &str) -> HttpRequest {
+    return HttpRequest {
+        method,
+        path: path.to_string(),
+        headers: Headers::new(),
+        body: Vec::new(),
+        params: HashMap::new(),
+        query: HashMap::new(),
+    };
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -131,10 +318,26 @@ mod tests {
 
     #[test]
     fn http_start_line_parses_successfully() {
-        let (method, path) =
-            parse_http_start_line("GET /api/index.html?a=1&b=2 HTTP/1.1".to_string());
+        let (method, path, query) =
+            parse_http_start_line("GET /api/index.html?a=1&b=2 HTTP/1.1".to_string()).unwrap();
         assert_eq!(method, HttpRequestMethod::Get);
-        assert_eq!(path, "/api/index.html?a=1&b=2");
+        assert_eq!(path, "/api/index.html");
+        assert_eq!(query["a"], "1");
+        assert_eq!(query["b"], "2");
+    }
+
+    #[test]
+    fn query_string_decodes_and_handles_edge_cases() {
+        let (_, path, query) =
+            parse_http_start_line("GET /search?q=hello+world&name=a%2Bb&flag HTTP/1.1".to_string())
+                .unwrap();
+        assert_eq!(path, "/search");
+        assert_eq!(query["q"], "hello world");
+        assert_eq!(query["name"], "a+b");
+        assert_eq!(query["flag"], "");
+
+        let (_, _, empty) = parse_http_start_line("GET /search? HTTP/1.1".to_string()).unwrap();
+        assert!(empty.is_empty());
     }
 
     #[test]
@@ -155,17 +358,26 @@ mod tests {
 
     #[test]
     fn http_request_parses_successfully() {
-        const RAW_REQUEST: &str = "DELETE /users/actor HTTP/1.1\nContent-Type: application/json\nContent-Length: 15\n\n{\"proceed\": true}";
+        const RAW_REQUEST: &str = "DELETE /users/actor HTTP/1.1\nContent-Type: application/json\nContent-Length: 17\n\n{\"proceed\": true}";
         match parse_http_request(&mut RAW_REQUEST.as_bytes()) {
             Ok(request) => {
                 assert_eq!(request.method, HttpRequestMethod::Delete);
                 assert_eq!(request.path, "/users/actor");
-                assert_eq!(request.headers.len(), 2);
-                assert_eq!(request.headers["Content-Type"], "application/json");
-                assert_eq!(request.headers["Content-Length"], "15");
-                assert_eq!(request.body, "{\"proceed\": true}");
+                assert_eq!(request.headers.get("content-type"), Some("application/json"));
+                assert_eq!(request.headers.get("Content-Length"), Some("17"));
+                assert_eq!(request.body, b"{\"proceed\": true}");
             }
             Err(_) => assert!(false),
         };
     }
+
+    #[test]
+    fn chunked_body_is_decoded() {
+        const RAW_REQUEST: &str =
+            "POST /upload HTTP/1.1\nTransfer-Encoding: chunked\n\n4\r\nWiki\r\n5\r\npedia\r\n0\r\n\r\n";
+        match parse_http_request(&mut RAW_REQUEST.as_bytes()) {
+            Ok(request) => assert_eq!(request.body, b"Wikipedia"),
+            Err(_) => assert!(false),
+        };
+    }
 }
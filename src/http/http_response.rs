@@ -0,0 +1,135 @@
+use std::{
+    collections::HashMap,
+    io::{Error, Write},
+};
+
+/// HTTP response status codes indicate whether a specific HTTP request has been
+/// successfully completed. Responses are grouped in five classes: informational,
+/// successful, redirection, client error and server error responses.
+/// source: https://developer.mozilla.org/en-US/docs/Web/HTTP/Status
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum HttpStatus {
+    Ok,
+    Created,
+    NoContent,
+    MovedPermanently,
+    Found,
+    NotModified,
+    BadRequest,
+    NotFound,
+    MethodNotAllowed,
+    InternalServerError,
+}
+
+impl HttpStatus {
+    /// Returns the numeric status code associated with this status.
+    pub fn code(&self) -> u16 {
+        return match self {
+            HttpStatus::Ok => 200,
+            HttpStatus::Created => 201,
+            HttpStatus::NoContent => 204,
+            HttpStatus::MovedPermanently => 301,
+            HttpStatus::Found => 302,
+            HttpStatus::NotModified => 304,
+            HttpStatus::BadRequest => 400,
+            HttpStatus::NotFound => 404,
+            HttpStatus::MethodNotAllowed => 405,
+            HttpStatus::InternalServerError => 500,
+        };
+    }
+
+    /// Returns the reason phrase that accompanies the status code on the status line.
+    pub fn reason_phrase(&self) -> &'static str {
+        return match self {
+            HttpStatus::Ok => "OK",
+            HttpStatus::Created => "Created",
+            HttpStatus::NoContent => "No Content",
+            HttpStatus::MovedPermanently => "Moved Permanently",
+            HttpStatus::Found => "Found",
+            HttpStatus::NotModified => "Not Modified",
+            HttpStatus::BadRequest => "Bad Request",
+            HttpStatus::NotFound => "Not Found",
+            HttpStatus::MethodNotAllowed => "Method Not Allowed",
+            HttpStatus::InternalServerError => "Internal Server Error",
+        };
+    }
+}
+
+/// An HTTP response made up of a status, a collection of headers and a body.
+/// Built fluently via [`HttpResponse::new`] and serialized onto any writer
+/// with [`HttpResponse::write_to`].
+pub struct HttpResponse {
+    pub status: HttpStatus,
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    /// Creates a new response with the given status and no headers or body.
+    pub fn new(status: HttpStatus) -> HttpResponse {
+        return HttpResponse {
+            status,
+            headers: HashMap::new(),
+            body: Vec::new(),
+        };
+    }
+
+    /// Sets a header on the response and returns the response for chaining.
+    pub fn header(mut self, name: &str, value: &str) -> HttpResponse {
+        self.headers.insert(name.to_string(), value.to_string());
+        return self;
+    }
+
+    /// Sets the response body and returns the response for chaining.
+    pub fn body(mut self, body: Vec<u8>) -> HttpResponse {
+        self.body = body;
+        return self;
+    }
+
+    /// Serializes the response onto the given writer, emitting a status line and
+    /// headers terminated by `\r\n`. `Content-Length` is always derived from the
+    /// body so callers cannot miscount it.
+    pub fn write_to<W: Write>(&self, w: &mut W) -> Result<(), Error> {
+        write!(
+            w,
+            "HTTP/1.1 {} {}\r\n",
+            self.status.code(),
+            self.status.reason_phrase()
+        )?;
+        for (name, value) in &self.headers {
+            if name.eq_ignore_ascii_case("Content-Length") {
+                continue;
+            }
+            write!(w, "{}: {}\r\n", name, value)?;
+        }
+        write!(w, "Content-Length: {}\r\n", self.body.len())?;
+        w.write_all(b"\r\n")?;
+        w.write_all(&self.body)?;
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HttpResponse, HttpStatus};
+
+    #[test]
+    fn http_status_exposes_code_and_reason() {
+        assert_eq!(HttpStatus::NotFound.code(), 404);
+        assert_eq!(HttpStatus::NotFound.reason_phrase(), "Not Found");
+    }
+
+    #[test]
+    fn http_response_writes_status_line_and_content_length() {
+        let response = HttpResponse::new(HttpStatus::Ok)
+            .header("Content-Type", "text/plain")
+            .body(b"ok".to_vec());
+        let mut buffer: Vec<u8> = Vec::new();
+        response.write_to(&mut buffer).unwrap();
+        let rendered = String::from_utf8(buffer).unwrap();
+        assert!(rendered.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(rendered.contains("Content-Type: text/plain\r\n"));
+        assert!(rendered.contains("Content-Length: 2\r\n"));
+        assert!(rendered.ends_with("\r\n\r\nok"));
+    }
+}
@@ -3,33 +3,56 @@ use std::{
     net::{TcpListener, TcpStream},
 };
 
-use crate::http::http_request::parse_http_request;
+use crate::http::headers::Headers;
+use crate::http::http_request::{parse_http_request_head, read_http_body, HttpParseError};
+use crate::http::http_response::{HttpResponse, HttpStatus};
+use crate::http::router::Router;
 
-pub fn start_http_server(port: u16) {
+pub fn start_http_server(port: u16, router: Router) {
     let listener = TcpListener::bind(format!("127.0.0.1:{}", port)).unwrap();
     for stream in listener.incoming() {
         let stream = stream.unwrap();
-        handle_connection(stream);
+        handle_connection(stream, &router);
     }
 }
 
-fn handle_connection(mut stream: TcpStream) {
+fn handle_connection(mut stream: TcpStream, router: &Router) {
     let mut buf_reader = BufReader::new(&mut stream);
-    match parse_http_request(&mut buf_reader) {
-        Ok(request) => {
-            println!("{}", request.path);
-            for (name, value) in request.headers {
-                println!("{}={}", name, value);
+    let response = match parse_http_request_head(&mut buf_reader) {
+        Ok(mut request) => {
+            // Honour `Expect: 100-continue` by acknowledging before the body is
+            // read, so clients that wait for a go-ahead do not stall.
+            if expects_continue(&request.headers) {
+                let _ = buf_reader
+                    .get_mut()
+                    .write_all(b"HTTP/1.1 100 Continue\r\n\r\n");
+            }
+            match read_http_body(&mut buf_reader, &request.headers) {
+                Ok(body) => {
+                    request.body = body;
+                    router.dispatch(&mut request)
+                }
+                Err(error) => bad_request(error),
             }
-            println!("{}", request.body);
-            stream
-                .write_all(
-                    "HTTP/1.1 200 OK\nContent-Length: 2\n\nok"
-                        .to_string()
-                        .as_bytes(),
-                )
-                .unwrap();
         }
-        Err(_) => println!("http request parse error"),
+        Err(error) => bad_request(error),
+    };
+    response.write_to(buf_reader.get_mut()).unwrap();
+}
+
+/// Builds a `400 Bad Request` response whose body carries the parse error's
+/// description, and logs the failure.
+fn bad_request(error: HttpParseError) -> HttpResponse {
+    let description = error.description();
+    println!("http request parse error: {}", description);
+    return HttpResponse::new(HttpStatus::BadRequest).body(description.into_bytes());
+}
+
+/// Returns whether the request headers ask the server to send an interim
+/// `100 Continue` response before the body is uploaded.
+fn expects_continue(headers: &Headers) -> bool {
+    return match headers.get("Expect") {
+        Some(value) => value.eq_ignore_ascii_case("100-continue"),
+        None => false,
     };
 }
@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+
+use crate::http::http_request::{HttpRequest, HttpRequestMethod};
+use crate::http::http_response::{HttpResponse, HttpStatus};
+
+/// A single registered route: the method and path pattern it answers to plus the
+/// handler invoked when a request matches.
+struct Route {
+    method: HttpRequestMethod,
+    pattern: String,
+    handler: Box<dyn Fn(&HttpRequest) -> HttpResponse>,
+}
+
+/// Dispatches requests to registered handlers based on the request method and a
+/// path pattern. Patterns may contain parameter segments like `/users/{id}`
+/// whose values are collected into [`HttpRequest::params`] before the matched
+/// handler runs.
+pub struct Router {
+    routes: Vec<Route>,
+}
+
+impl Router {
+    /// Creates an empty router with no registered routes.
+    pub fn new() -> Router {
+        return Router { routes: Vec::new() };
+    }
+
+    /// Registers a handler for the given method and path pattern, returning the
+    /// router for chaining.
+    pub fn route<H>(mut self, method: HttpRequestMethod, pattern: &str, handler: H) -> Router
+    where
+        H: Fn(&HttpRequest) -> HttpResponse + 'static,
+    {
+        self.routes.push(Route {
+            method,
+            pattern: pattern.to_string(),
+            handler: Box::new(handler),
+        });
+        return self;
+    }
+
+    /// Matches the request against the registered routes and returns the handler
+    /// response. Returns 404 when no pattern matches the path, and 405 with an
+    /// `Allow` header when the path matches but the method does not.
+    pub fn dispatch(&self, request: &mut HttpRequest) -> HttpResponse {
+        let mut path_matched = false;
+        for route in &self.routes {
+            match match_pattern(&route.pattern, &request.path) {
+                Some(params) => {
+                    path_matched = true;
+                    if route.method == request.method {
+                        request.params = params;
+                        return (route.handler)(request);
+                    }
+                }
+                None => {}
+            }
+        }
+        if path_matched {
+            let allowed = self.allowed_methods(&request.path);
+            return HttpResponse::new(HttpStatus::MethodNotAllowed)
+                .header("Allow", &allowed.join(", "));
+        }
+        return HttpResponse::new(HttpStatus::NotFound);
+    }
+
+    /// Collects the methods registered for any pattern matching the given path,
+    /// used to populate the `Allow` header on a 405 response.
+    fn allowed_methods(&self, path: &str) -> Vec<&'static str> {
+        let mut methods: Vec<&'static str> = Vec::new();
+        for route in &self.routes {
+            if match_pattern(&route.pattern, path).is_some() {
+                let name = route.method.as_str();
+                if !methods.contains(&name) {
+                    methods.push(name);
+                }
+            }
+        }
+        return methods;
+    }
+}
+
+/// Matches a path pattern against a request path segment-by-segment. On success
+/// returns the collected `{name}` parameters; on a length or literal mismatch
+/// returns `None`.
+fn match_pattern(pattern: &str, path: &str) -> Option<HashMap<String, String>> {
+    let pattern_segments: Vec<&str> = pattern.trim_matches('/').split('/').collect();
+    let path_segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    if pattern_segments.len() != path_segments.len() {
+        return None;
+    }
+    let mut params: HashMap<String, String> = HashMap::new();
+    for (pattern_segment, path_segment) in pattern_segments.iter().zip(path_segments.iter()) {
+        if pattern_segment.starts_with('{') && pattern_segment.ends_with('}') {
+            let name = &pattern_segment[1..pattern_segment.len() - 1];
+            params.insert(name.to_string(), path_segment.to_string());
+        } else if pattern_segment != path_segment {
+            return None;
+        }
+    }
+    return Some(params);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Router;
+    use crate::http::http_request::{test_request as request, HttpRequestMethod};
+    use crate::http::http_response::HttpStatus;
+
+    #[test]
+    fn router_matches_path_parameters() {
+        let router = Router::new().route(HttpRequestMethod::Get, "/users/{id}", |req| {
+            crate::http::http_response::HttpResponse::new(HttpStatus::Ok)
+                .body(req.params["id"].clone().into_bytes())
+        });
+        let mut req = request(HttpRequestMethod::Get, "/users/actor");
+        let response = router.dispatch(&mut req);
+        assert_eq!(response.status, HttpStatus::Ok);
+        assert_eq!(response.body, b"actor");
+    }
+
+    #[test]
+    fn router_returns_404_on_no_match() {
+        let router = Router::new();
+        let mut req = request(HttpRequestMethod::Get, "/missing");
+        assert_eq!(router.dispatch(&mut req).status, HttpStatus::NotFound);
+    }
+
+    #[test]
+    fn router_returns_405_with_allow_header() {
+        let router = Router::new().route(HttpRequestMethod::Get, "/users", |_| {
+            crate::http::http_response::HttpResponse::new(HttpStatus::Ok)
+        });
+        let mut req = request(HttpRequestMethod::Post, "/users");
+        let response = router.dispatch(&mut req);
+        assert_eq!(response.status, HttpStatus::MethodNotAllowed);
+        assert_eq!(response.headers["Allow"], "GET");
+    }
+}